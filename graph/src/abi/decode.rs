@@ -0,0 +1,240 @@
+//! Hand-maintained decode helpers layered on top of the Abigen-generated
+//! bindings in `factory.rs`/`vault.rs`.
+//!
+//! The generated `Event::match_log` gates on topic count as well as
+//! topic0, and the generated `Event::decode` rejects any data word that
+//! isn't exactly 32 bytes. That makes a log that reuses our topic0 with a
+//! different topic layout or a short data word indistinguishable, from
+//! `map_events`'s point of view, from a log that was never ours to begin
+//! with — both just make `Event::match_and_decode` return `None` and
+//! vanish, with no entry in `LogDiagnostics`. `map_events` needs to tell those two cases apart, so
+//! this module gates on topic0 only (`is_vault_created`/
+//! `is_position_opened`) and leaves shape validation to `decode_checked`,
+//! which reports *why* a same-signature log didn't decode instead of
+//! treating it like it was never ours.
+//!
+//! This file is not touched by `build.rs`/Abigen, so it survives
+//! regenerating `factory.rs`/`vault.rs`. The topic0 constants below are
+//! intentionally duplicated from those files' private `TOPIC_ID`s — if an
+//! ABI changes and the bindings are regenerated, update the matching
+//! constant here too.
+
+use super::factory::events::VaultCreated;
+use super::vault::events::PositionOpened;
+use substreams::scalar::BigInt;
+use substreams_ethereum::pb::eth::v2 as eth;
+
+const VAULT_CREATED_TOPIC_ID: [u8; 32] = [
+    0xb9, 0xf8, 0x4b, 0x8e, 0x65, 0x16, 0x4b, 0x14, 0x43, 0x9a, 0xe3, 0x62, 0x05, 0x19, 0xba, 0x4d,
+    0x2a, 0xf4, 0xc9, 0x6b, 0x13, 0x96, 0xb1, 0x77, 0x29, 0x46, 0xe8, 0x97, 0x15, 0x9a, 0x45, 0xa7,
+];
+
+const POSITION_OPENED_TOPIC_ID: [u8; 32] = [
+    0x3c, 0x92, 0xd6, 0x99, 0xa2, 0xf0, 0xcd, 0x97, 0x42, 0xc8, 0xa1, 0x4e, 0xba, 0x5a, 0x8a, 0xd4,
+    0xb5, 0x14, 0xa4, 0x80, 0xee, 0x8a, 0x29, 0x7e, 0x33, 0x04, 0xa1, 0xe9, 0x7c, 0x2b, 0x33, 0x2d,
+];
+
+/// Distinct reasons a same-topic0 log can fail to decode, so callers can
+/// account for them separately instead of merging every failure into one
+/// counter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    InvalidTopicCount,
+    InvalidDataLength,
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::InvalidTopicCount => {
+                write!(f, "topic count doesn't match this event's signature")
+            }
+            DecodeError::InvalidDataLength => {
+                write!(f, "expected at most a single 32-byte data word")
+            }
+        }
+    }
+}
+
+/// True if `log`'s topic0 matches `VaultCreated`'s signature, regardless
+/// of topic count or data length — use `decode_vault_created` to validate
+/// the rest of the shape.
+pub fn is_vault_created(log: &eth::Log) -> bool {
+    log.topics
+        .get(0)
+        .map(|topic0| topic0.as_slice() == VAULT_CREATED_TOPIC_ID)
+        .unwrap_or(false)
+}
+
+/// True if `log`'s topic0 matches `PositionOpened`'s signature,
+/// regardless of topic count or data length — use
+/// `decode_position_opened` to validate the rest of the shape.
+pub fn is_position_opened(log: &eth::Log) -> bool {
+    log.topics
+        .get(0)
+        .map(|topic0| topic0.as_slice() == POSITION_OPENED_TOPIC_ID)
+        .unwrap_or(false)
+}
+
+pub fn decode_vault_created(log: &eth::Log) -> Result<VaultCreated, DecodeError> {
+    if log.topics.len() != 3usize {
+        return Err(DecodeError::InvalidTopicCount);
+    }
+    if log.data.len() > 32usize {
+        return Err(DecodeError::InvalidDataLength);
+    }
+
+    Ok(VaultCreated {
+        token0: log.topics[1][12..].to_vec(),
+        token1: log.topics[2][12..].to_vec(),
+        vault_id: decode_uint256(&log.data),
+    })
+}
+
+pub fn decode_position_opened(log: &eth::Log) -> Result<PositionOpened, DecodeError> {
+    if log.topics.len() != 4usize {
+        return Err(DecodeError::InvalidTopicCount);
+    }
+    if log.data.len() > 32usize {
+        return Err(DecodeError::InvalidDataLength);
+    }
+
+    Ok(PositionOpened {
+        owner: log.topics[1][12..].to_vec(),
+        amount0: BigInt::from_unsigned_bytes_be(&log.topics[2]),
+        amount1: BigInt::from_unsigned_bytes_be(&log.topics[3]),
+        position_id: decode_uint256(&log.data),
+    })
+}
+
+/// Decodes a big-endian word into a `BigInt`, left-padding slices shorter
+/// than 32 bytes instead of rejecting them.
+fn decode_uint256(word: &[u8]) -> BigInt {
+    if word.len() >= 32 {
+        BigInt::from_unsigned_bytes_be(&word[word.len() - 32..])
+    } else {
+        let mut padded = [0u8; 32];
+        padded[32 - word.len()..].copy_from_slice(word);
+        BigInt::from_unsigned_bytes_be(&padded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn topic(last_byte: u8) -> Vec<u8> {
+        let mut word = vec![0u8; 32];
+        word[31] = last_byte;
+        word
+    }
+
+    fn valid_vault_created_log() -> eth::Log {
+        eth::Log {
+            topics: vec![VAULT_CREATED_TOPIC_ID.to_vec(), topic(1), topic(2)],
+            data: vec![7u8],
+            ..Default::default()
+        }
+    }
+
+    fn valid_position_opened_log() -> eth::Log {
+        eth::Log {
+            topics: vec![
+                POSITION_OPENED_TOPIC_ID.to_vec(),
+                topic(1),
+                topic(2),
+                topic(3),
+            ],
+            data: vec![9u8],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn decode_vault_created_rejects_wrong_topic_count() {
+        let mut log = valid_vault_created_log();
+        log.topics.pop();
+
+        assert_eq!(decode_vault_created(&log), Err(DecodeError::InvalidTopicCount));
+    }
+
+    #[test]
+    fn decode_vault_created_rejects_oversized_data() {
+        let mut log = valid_vault_created_log();
+        log.data = vec![0u8; 33];
+
+        assert_eq!(decode_vault_created(&log), Err(DecodeError::InvalidDataLength));
+    }
+
+    #[test]
+    fn decode_vault_created_left_pads_short_data() {
+        let log = valid_vault_created_log();
+
+        let event = decode_vault_created(&log).expect("valid log decodes");
+        assert_eq!(event.vault_id, BigInt::from(7u64));
+    }
+
+    #[test]
+    fn decode_position_opened_rejects_wrong_topic_count() {
+        let mut log = valid_position_opened_log();
+        log.topics.pop();
+
+        assert_eq!(
+            decode_position_opened(&log),
+            Err(DecodeError::InvalidTopicCount)
+        );
+    }
+
+    #[test]
+    fn decode_position_opened_rejects_oversized_data() {
+        let mut log = valid_position_opened_log();
+        log.data = vec![0u8; 33];
+
+        assert_eq!(
+            decode_position_opened(&log),
+            Err(DecodeError::InvalidDataLength)
+        );
+    }
+
+    #[test]
+    fn decode_position_opened_left_pads_short_data() {
+        let log = valid_position_opened_log();
+
+        let event = decode_position_opened(&log).expect("valid log decodes");
+        assert_eq!(event.position_id, BigInt::from(9u64));
+    }
+
+    #[test]
+    fn is_vault_created_ignores_other_topic0() {
+        let mut log = valid_vault_created_log();
+        log.topics[0] = topic(0xff);
+
+        assert!(!is_vault_created(&log));
+    }
+
+    #[test]
+    fn is_vault_created_accepts_same_topic0_with_wrong_topic_count() {
+        // The spoofing case `Event::match_log` can't distinguish from an
+        // unrelated log: same signature, different shape. `is_vault_created`
+        // must still say yes so `map_events` routes it to
+        // `decode_vault_created` (and into `LogDiagnostics`) instead of
+        // silently dropping it like a log that was never ours.
+        let mut log = valid_vault_created_log();
+        log.topics.pop();
+
+        assert!(is_vault_created(&log));
+        assert_eq!(decode_vault_created(&log), Err(DecodeError::InvalidTopicCount));
+    }
+
+    #[test]
+    fn is_position_opened_accepts_same_topic0_with_wrong_topic_count() {
+        let mut log = valid_position_opened_log();
+        log.topics.pop();
+
+        assert!(is_position_opened(&log));
+        assert_eq!(
+            decode_position_opened(&log),
+            Err(DecodeError::InvalidTopicCount)
+        );
+    }
+}
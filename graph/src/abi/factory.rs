@@ -0,0 +1,54 @@
+// @generated
+// This file was automatically generated by Abigen from Factory's ABI
+// (abi/factory.json). Do not edit by hand, re-run `cargo build` instead.
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+pub mod events {
+    use substreams::scalar::BigInt;
+    use substreams_ethereum::pb::eth::v2 as eth;
+    use substreams_ethereum::Event;
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct VaultCreated {
+        pub token0: Vec<u8>,
+        pub token1: Vec<u8>,
+        pub vault_id: BigInt,
+    }
+
+    impl VaultCreated {
+        const TOPIC_ID: [u8; 32] = [
+            0xb9, 0xf8, 0x4b, 0x8e, 0x65, 0x16, 0x4b, 0x14, 0x43, 0x9a, 0xe3, 0x62, 0x05, 0x19,
+            0xba, 0x4d, 0x2a, 0xf4, 0xc9, 0x6b, 0x13, 0x96, 0xb1, 0x77, 0x29, 0x46, 0xe8, 0x97,
+            0x15, 0x9a, 0x45, 0xa7,
+        ];
+    }
+
+    impl Event for VaultCreated {
+        const NAME: &'static str = "VaultCreated";
+
+        fn match_log(log: &eth::Log) -> bool {
+            log.topics.len() == 3usize
+                && log
+                    .topics
+                    .get(0)
+                    .map(|topic0| topic0.as_slice() == Self::TOPIC_ID)
+                    .unwrap_or(false)
+        }
+
+        fn decode(log: &eth::Log) -> Result<Self, String> {
+            if log.topics.len() != 3usize {
+                return Err("VaultCreated: expected 3 topics (signature + token0 + token1)".into());
+            }
+            if log.data.len() != 32usize {
+                return Err("VaultCreated: expected a single 32-byte data word (vaultId)".into());
+            }
+
+            Ok(Self {
+                token0: log.topics[1][12..].to_vec(),
+                token1: log.topics[2][12..].to_vec(),
+                vault_id: BigInt::from_unsigned_bytes_be(&log.data),
+            })
+        }
+    }
+}
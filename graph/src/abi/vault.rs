@@ -0,0 +1,59 @@
+// @generated
+// This file was automatically generated by Abigen from Vault's ABI
+// (abi/vault.json). Do not edit by hand, re-run `cargo build` instead.
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+pub mod events {
+    use substreams::scalar::BigInt;
+    use substreams_ethereum::pb::eth::v2 as eth;
+    use substreams_ethereum::Event;
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct PositionOpened {
+        pub owner: Vec<u8>,
+        pub amount0: BigInt,
+        pub amount1: BigInt,
+        pub position_id: BigInt,
+    }
+
+    impl PositionOpened {
+        const TOPIC_ID: [u8; 32] = [
+            0x3c, 0x92, 0xd6, 0x99, 0xa2, 0xf0, 0xcd, 0x97, 0x42, 0xc8, 0xa1, 0x4e, 0xba, 0x5a,
+            0x8a, 0xd4, 0xb5, 0x14, 0xa4, 0x80, 0xee, 0x8a, 0x29, 0x7e, 0x33, 0x04, 0xa1, 0xe9,
+            0x7c, 0x2b, 0x33, 0x2d,
+        ];
+    }
+
+    impl Event for PositionOpened {
+        const NAME: &'static str = "PositionOpened";
+
+        fn match_log(log: &eth::Log) -> bool {
+            log.topics.len() == 4usize
+                && log
+                    .topics
+                    .get(0)
+                    .map(|topic0| topic0.as_slice() == Self::TOPIC_ID)
+                    .unwrap_or(false)
+        }
+
+        fn decode(log: &eth::Log) -> Result<Self, String> {
+            if log.topics.len() != 4usize {
+                return Err(
+                    "PositionOpened: expected 4 topics (signature + owner + amount0 + amount1)"
+                        .into(),
+                );
+            }
+            if log.data.len() != 32usize {
+                return Err("PositionOpened: expected a single 32-byte data word (positionId)".into());
+            }
+
+            Ok(Self {
+                owner: log.topics[1][12..].to_vec(),
+                amount0: BigInt::from_unsigned_bytes_be(&log.topics[2]),
+                amount1: BigInt::from_unsigned_bytes_be(&log.topics[3]),
+                position_id: BigInt::from_unsigned_bytes_be(&log.data),
+            })
+        }
+    }
+}
@@ -1,6 +1,12 @@
+mod abi;
 mod pb;
+mod store;
+mod time;
 
-use pb::example::{Events, Vault, Position};
+use abi::decode::DecodeError;
+use pb::example::{Events, LogDiagnostics, Vault, Position};
+use substreams::scalar::BigInt;
+use substreams::store::{StoreGet, StoreGetBigInt, StoreGetInt64};
 use substreams::Hex;
 use substreams_entity_change::pb::entity::EntityChanges;
 use substreams_entity_change::tables::Tables;
@@ -8,90 +14,204 @@ use substreams_ethereum::pb::eth;
 use hex;
 
 const FACTORY_ADDRESS: &str = "0x008D4Dd934f9811E768F71AbCe59E193DC407CF8";
-const VAULT_CREATED_SIG: &str = "0xb9f84b8e65164b14439ae3620519ba4d2af4c96b1396b1772946e897159a45a7";
-const POSITION_OPENED_SIG: &str = "0x3c92d699a2f0cd9742c8a14eba5a8ad4b514a480ee8a297e3304a1e97c2b332d";
 
 #[substreams::handlers::map]
 fn map_events(block: eth::v2::Block) -> Result<Events, substreams::errors::Error> {
     let mut vaults = Vec::new();
     let mut positions = Vec::new();
+    let mut vault_created_invalid_topic_count = 0i64;
+    let mut vault_created_invalid_data_length = 0i64;
+    let mut position_opened_invalid_topic_count = 0i64;
+    let mut position_opened_invalid_data_length = 0i64;
+
+    let ts = time::normalize(block.timestamp_seconds() as i64);
+    let factory_address =
+        hex::decode(FACTORY_ADDRESS.trim_start_matches("0x")).expect("valid factory address");
 
     for log in block.logs() {
-        if is_vault_created_event(&log.log) {
-            let vault = Vault {
-                address: format!("0x{}", Hex(&log.log.address)),
-                token0: format!("0x{}", Hex(&log.log.topics[1])),
-                token1: format!("0x{}", Hex(&log.log.topics[2])),
-                vault_id: decode_uint256(&log.log.data) as u64,
-                block_number: block.number,
-                timestamp: block.timestamp_seconds().to_string(),
-                factory: FACTORY_ADDRESS.to_string(),
-            };
-            vaults.push(vault);
+        // `abi::decode::is_vault_created`/`is_position_opened` gate on
+        // topic0 alone (unlike the generated `Event::match_log`, which
+        // also gates on topic count), so a log that reuses our topic0
+        // with a different shape still reaches `decode_vault_created`/
+        // `decode_position_opened` and is reported as a decode error
+        // instead of silently vanishing alongside the vast majority of
+        // logs that simply aren't ours.
+        //
+        // A `VaultCreated`-shaped log is only trusted if it was actually
+        // emitted by the tracked factory; anything else is a spoofed event
+        // from an unrelated contract and must never reach
+        // `store_known_vaults`, or `filter_events` would let that
+        // contract's positions through too.
+        if abi::decode::is_vault_created(&log.log) && log.log.address == factory_address {
+            match abi::decode::decode_vault_created(&log.log) {
+                Ok(event) => vaults.push(Vault {
+                    address: format!("0x{}", Hex(&log.log.address)),
+                    token0: format!("0x{}", Hex(&event.token0)),
+                    token1: format!("0x{}", Hex(&event.token1)),
+                    vault_id: event.vault_id.to_string(),
+                    vault_id_hex: format!("0x{}", Hex(&log.log.data)),
+                    block_number: block.number,
+                    timestamp_unix: ts.unix,
+                    timestamp_iso8601: ts.iso8601.clone(),
+                    timestamp_epoch_offset: ts.epoch_offset,
+                    factory: FACTORY_ADDRESS.to_string(),
+                }),
+                Err(DecodeError::InvalidTopicCount) => vault_created_invalid_topic_count += 1,
+                Err(DecodeError::InvalidDataLength) => vault_created_invalid_data_length += 1,
+            }
         }
 
-        if is_position_opened_event(&log.log) {
-            let position = Position {
-                position_id: decode_uint256(&log.log.data) as u64,
-                owner: format!("0x{}", Hex(&log.log.topics[1])),
-                amount0: format!("0x{}", Hex(&log.log.topics[2])),
-                amount1: format!("0x{}", Hex(&log.log.topics[3])),
-                block_number: block.number,
-                timestamp: block.timestamp_seconds().to_string(),
-                vault: format!("0x{}", Hex(&log.log.address)),
-            };
-            positions.push(position);
+        if abi::decode::is_position_opened(&log.log) {
+            match abi::decode::decode_position_opened(&log.log) {
+                Ok(event) => positions.push(Position {
+                    position_id: event.position_id.to_string(),
+                    position_id_hex: format!("0x{}", Hex(&log.log.data)),
+                    owner: format!("0x{}", Hex(&event.owner)),
+                    amount0: event.amount0.to_string(),
+                    amount0_hex: format!("0x{}", Hex(&log.log.topics[2])),
+                    amount1: event.amount1.to_string(),
+                    amount1_hex: format!("0x{}", Hex(&log.log.topics[3])),
+                    block_number: block.number,
+                    timestamp_unix: ts.unix,
+                    timestamp_iso8601: ts.iso8601.clone(),
+                    timestamp_epoch_offset: ts.epoch_offset,
+                    vault: format!("0x{}", Hex(&log.log.address)),
+                }),
+                Err(DecodeError::InvalidTopicCount) => position_opened_invalid_topic_count += 1,
+                Err(DecodeError::InvalidDataLength) => position_opened_invalid_data_length += 1,
+            }
         }
     }
 
-    Ok(Events { vaults, positions })
+    let diagnostics = if vault_created_invalid_topic_count > 0
+        || vault_created_invalid_data_length > 0
+        || position_opened_invalid_topic_count > 0
+        || position_opened_invalid_data_length > 0
+    {
+        Some(LogDiagnostics {
+            block_number: block.number,
+            vault_created_invalid_topic_count,
+            vault_created_invalid_data_length,
+            position_opened_invalid_topic_count,
+            position_opened_invalid_data_length,
+        })
+    } else {
+        None
+    };
+
+    Ok(Events {
+        vaults,
+        positions,
+        diagnostics,
+    })
+}
+
+/// Drops any `Position` whose vault address was not created by the
+/// tracked factory (per `store_known_vaults`), so the pipeline stays
+/// self-filtering as new vaults appear without hard-coding addresses.
+///
+/// Untested for the same reason as the `store.rs` handlers: `known_vaults`
+/// is a `substreams::store::StoreGetInt64`, constructible only via that
+/// crate's test-store harness, which this manifest-less snapshot can't
+/// pin a version of.
+#[substreams::handlers::map]
+pub fn filter_events(
+    events: Events,
+    known_vaults: StoreGetInt64,
+) -> Result<Events, substreams::errors::Error> {
+    let positions = events
+        .positions
+        .into_iter()
+        .filter(|position| known_vaults.get_last(&position.vault).is_some())
+        .collect();
+
+    Ok(Events {
+        vaults: events.vaults,
+        positions,
+        diagnostics: events.diagnostics,
+    })
 }
 
+/// `events` and every store input here bind, in the manifest, to
+/// `filter_events`'s output rather than `map_events`'s raw output — see
+/// the module doc in `store.rs` for why that matters.
 #[substreams::handlers::map]
-pub fn graph_out(events: Events) -> Result<EntityChanges, substreams::errors::Error> {
+pub fn graph_out(
+    events: Events,
+    vault_tvl: StoreGetBigInt,
+    position_counts: StoreGetInt64,
+    unique_owners: StoreGetInt64,
+) -> Result<EntityChanges, substreams::errors::Error> {
     let mut tables = Tables::new();
 
     for vault in events.vaults {
+        let tvl_token0 = vault_tvl
+            .get_last(format!("token0:{}", vault.address))
+            .unwrap_or_else(BigInt::zero);
+        let tvl_token1 = vault_tvl
+            .get_last(format!("token1:{}", vault.address))
+            .unwrap_or_else(BigInt::zero);
+        let total_positions = position_counts.get_last(&vault.address).unwrap_or(0);
+        let unique_owner_count = unique_owners
+            .get_last(format!("count:{}", vault.address))
+            .unwrap_or(0);
+
         tables
             .create_row("Vault", vault.address.clone())
             .set("address", vault.address)
             .set("token0", vault.token0)
             .set("token1", vault.token1)
             .set("vaultId", vault.vault_id)
-            .set("timestamp", vault.timestamp)
+            .set("vaultIdHex", vault.vault_id_hex)
+            .set("timestampUnix", vault.timestamp_unix)
+            .set("timestampIso8601", vault.timestamp_iso8601)
+            .set("timestampEpochOffset", vault.timestamp_epoch_offset)
             .set("blockNumber", vault.block_number)
-            .set("factory", vault.factory);
+            .set("factory", vault.factory)
+            .set("totalPositions", total_positions)
+            .set("tvlToken0", tvl_token0.to_string())
+            .set("tvlToken1", tvl_token1.to_string())
+            .set("uniqueOwners", unique_owner_count);
     }
 
     for position in events.positions {
         tables
-            .create_row("Position", position.position_id.to_string())
+            .create_row("Position", position.position_id.clone())
             .set("positionId", position.position_id)
+            .set("positionIdHex", position.position_id_hex)
             .set("owner", position.owner)
             .set("amount0", position.amount0)
+            .set("amount0Hex", position.amount0_hex)
             .set("amount1", position.amount1)
-            .set("timestamp", position.timestamp)
+            .set("amount1Hex", position.amount1_hex)
+            .set("timestampUnix", position.timestamp_unix)
+            .set("timestampIso8601", position.timestamp_iso8601)
+            .set("timestampEpochOffset", position.timestamp_epoch_offset)
             .set("blockNumber", position.block_number)
             .set("vault", position.vault);
     }
 
-    Ok(tables.to_entity_changes())
-}
-
-fn is_vault_created_event(log: &eth::v2::Log) -> bool {
-    let topic0 = &log.topics[0];
-    let sig = hex::decode(VAULT_CREATED_SIG.trim_start_matches("0x")).unwrap();
-    topic0 == sig.as_slice()
-}
+    if let Some(diagnostics) = events.diagnostics {
+        tables
+            .create_row("LogDiagnostics", diagnostics.block_number.to_string())
+            .set("blockNumber", diagnostics.block_number)
+            .set(
+                "vaultCreatedInvalidTopicCount",
+                diagnostics.vault_created_invalid_topic_count,
+            )
+            .set(
+                "vaultCreatedInvalidDataLength",
+                diagnostics.vault_created_invalid_data_length,
+            )
+            .set(
+                "positionOpenedInvalidTopicCount",
+                diagnostics.position_opened_invalid_topic_count,
+            )
+            .set(
+                "positionOpenedInvalidDataLength",
+                diagnostics.position_opened_invalid_data_length,
+            );
+    }
 
-fn is_position_opened_event(log: &eth::v2::Log) -> bool {
-    let topic0 = &log.topics[0];
-    let sig = hex::decode(POSITION_OPENED_SIG.trim_start_matches("0x")).unwrap();
-    topic0 == sig.as_slice()
+    Ok(tables.to_entity_changes())
 }
-
-fn decode_uint256(data: &[u8]) -> u64 {
-    let mut bytes = [0u8; 8];
-    bytes.copy_from_slice(&data[24..32]);
-    u64::from_be_bytes(bytes)
-}
\ No newline at end of file
@@ -0,0 +1,78 @@
+// @generated
+// Generated from proto/example.proto. Do not edit by hand.
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Events {
+    #[prost(message, repeated, tag = "1")]
+    pub vaults: ::prost::alloc::vec::Vec<Vault>,
+    #[prost(message, repeated, tag = "2")]
+    pub positions: ::prost::alloc::vec::Vec<Position>,
+    #[prost(message, optional, tag = "3")]
+    pub diagnostics: ::core::option::Option<LogDiagnostics>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LogDiagnostics {
+    #[prost(uint64, tag = "1")]
+    pub block_number: u64,
+    #[prost(int64, tag = "2")]
+    pub vault_created_invalid_topic_count: i64,
+    #[prost(int64, tag = "3")]
+    pub vault_created_invalid_data_length: i64,
+    #[prost(int64, tag = "4")]
+    pub position_opened_invalid_topic_count: i64,
+    #[prost(int64, tag = "5")]
+    pub position_opened_invalid_data_length: i64,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Vault {
+    #[prost(string, tag = "1")]
+    pub address: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub token0: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub token1: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub vault_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub vault_id_hex: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "6")]
+    pub block_number: u64,
+    #[prost(int64, tag = "7")]
+    pub timestamp_unix: i64,
+    #[prost(string, tag = "8")]
+    pub timestamp_iso8601: ::prost::alloc::string::String,
+    #[prost(int64, tag = "9")]
+    pub timestamp_epoch_offset: i64,
+    #[prost(string, tag = "10")]
+    pub factory: ::prost::alloc::string::String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Position {
+    #[prost(string, tag = "1")]
+    pub position_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub position_id_hex: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub owner: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub amount0: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub amount0_hex: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub amount1: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub amount1_hex: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "8")]
+    pub block_number: u64,
+    #[prost(int64, tag = "9")]
+    pub timestamp_unix: i64,
+    #[prost(string, tag = "10")]
+    pub timestamp_iso8601: ::prost::alloc::string::String,
+    #[prost(int64, tag = "11")]
+    pub timestamp_epoch_offset: i64,
+    #[prost(string, tag = "12")]
+    pub vault: ::prost::alloc::string::String,
+}
@@ -0,0 +1,89 @@
+//! Store subsystem for per-vault aggregates (TVL, position counts, unique
+//! owners).
+//!
+//! Every handler below *except `store_known_vaults`*, and the matching
+//! store inputs `graph_out` reads, are wired in the manifest to
+//! `filter_events`'s output — never to `map_events`'s raw output.
+//! `filter_events` is what drops positions from vaults the factory never
+//! created; pointing any of these at `map_events` directly would let a
+//! spoofed `PositionOpened` log inflate
+//! `tvlToken0`/`tvlToken1`/`totalPositions`/`uniqueOwners` even though the
+//! corresponding `Position` row is correctly absent from the subgraph.
+//!
+//! `store_known_vaults` is the one exception, and must stay one: it binds
+//! to `map_events`'s raw output instead. `filter_events` takes this
+//! store's output as an input, so wiring `store_known_vaults` to
+//! `filter_events`'s output would make a cycle (`filter_events` needs
+//! `store_known_vaults`'s store, which would need `filter_events`'s
+//! events). This is safe because `map_events` already gates `VaultCreated`
+//! on the factory address before it ever reaches `events.vaults`, so
+//! `store_known_vaults` never records a spoofed vault regardless of which
+//! producer it binds to.
+//!
+//! None of the handlers below have direct-call unit tests: they take
+//! `substreams::store` types (`StoreAddBigInt`, `StoreGetInt64`, ...)
+//! whose constructors come from the `substreams` crate's own test-store
+//! harness, and this snapshot has no manifest pinning which version of
+//! that crate (or test-store API) is available to build against. Cover
+//! `store_unique_owners`'s first-seen logic with that harness once a
+//! manifest exists, rather than guessing at its API here.
+
+use crate::pb::example::Events;
+use substreams::store::{
+    StoreAddBigInt, StoreAddInt64, StoreGet, StoreGetInt64, StoreNew, StoreSetIfNotSet,
+};
+use substreams::scalar::BigInt;
+
+/// Accumulates cumulative deposited `amount0`/`amount1` per vault, keyed
+/// `token0:{vault}` / `token1:{vault}`, for the `tvlToken0`/`tvlToken1`
+/// fields on `Vault`.
+#[substreams::handlers::store]
+pub fn store_vault_tvl(events: Events, store: StoreAddBigInt) {
+    for (ord, position) in events.positions.iter().enumerate() {
+        let amount0 = position.amount0.parse::<BigInt>().unwrap_or_else(|_| BigInt::zero());
+        let amount1 = position.amount1.parse::<BigInt>().unwrap_or_else(|_| BigInt::zero());
+        store.add(ord as u64, format!("token0:{}", position.vault), amount0);
+        store.add(ord as u64, format!("token1:{}", position.vault), amount1);
+    }
+}
+
+/// Accumulates the number of positions opened per vault, for the
+/// `totalPositions` field on `Vault`.
+#[substreams::handlers::store]
+pub fn store_position_count(events: Events, store: StoreAddInt64) {
+    for (ord, position) in events.positions.iter().enumerate() {
+        store.add(ord as u64, position.vault.clone(), 1);
+    }
+}
+
+/// Records every vault address created by the tracked factory, so that
+/// `filter_events` can drop `Position`s emitted by contracts the factory
+/// never created (e.g. spoofed `PositionOpened` logs).
+#[substreams::handlers::store]
+pub fn store_known_vaults(events: Events, store: StoreSetIfNotSet<i64>) {
+    for (ord, vault) in events.vaults.iter().enumerate() {
+        store.set_if_not_set(ord as u64, vault.address.clone(), &1);
+    }
+}
+
+/// Adds one to a vault's unique-owner count (keyed `count:{vault}`) the
+/// first time its owner is seen, and records that owner as seen (keyed
+/// `owner:{vault}:{owner}`) — both reads and writes go through this same
+/// module's own output store (`self_store`/`store` are the same
+/// self-referencing store, the standard substreams pattern for a
+/// first-seen check). This is deliberate: splitting the "have we seen
+/// this owner" check into one store module and the count into another
+/// only stays correct if both enumerate `events.positions` in lockstep,
+/// an invariant a future wiring change could silently break. Doing both
+/// in one module removes that coupling entirely.
+#[substreams::handlers::store]
+pub fn store_unique_owners(events: Events, self_store: StoreGetInt64, store: StoreAddInt64) {
+    for (ord, position) in events.positions.iter().enumerate() {
+        let ord = ord as u64;
+        let owner_key = format!("owner:{}:{}", position.vault, position.owner);
+        if self_store.get_at(ord, &owner_key).is_none() {
+            store.add(ord, format!("count:{}", position.vault), 1);
+        }
+        store.add(ord, owner_key, 1);
+    }
+}
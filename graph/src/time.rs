@@ -0,0 +1,74 @@
+// Requires the `chrono` crate (its default `Utc`/`TimeZone` exports, as
+// used below) declared in Cargo.toml. This snapshot has no manifest, so
+// whoever owns it needs to add `chrono` alongside `substreams`,
+// `substreams-ethereum`, and `hex` before this builds.
+use chrono::{TimeZone, Utc};
+
+/// Unix-seconds timestamp of the factory's deployment block, used as the
+/// zero point for `epoch_offset`. Update this if the factory is ever
+/// redeployed to track a different protocol genesis.
+pub const EPOCH_OFFSET: i64 = 1_700_000_000;
+
+/// Raw unix seconds, an RFC3339/ISO-8601 string, and seconds relative to
+/// `EPOCH_OFFSET`, derived from a single block timestamp.
+pub struct Timestamps {
+    pub unix: i64,
+    pub iso8601: String,
+    pub epoch_offset: i64,
+}
+
+/// Normalizes a block's unix-second timestamp into the three forms
+/// entities store: a typed unix value (avoids lexical-string sorting
+/// bugs), a human-readable RFC3339 string, and an epoch-relative value
+/// that makes time-bucketed aggregation a cheap integer subtraction.
+pub fn normalize(unix_seconds: i64) -> Timestamps {
+    let iso8601 = Utc
+        .timestamp_opt(unix_seconds, 0)
+        .single()
+        .map(|dt| dt.to_rfc3339())
+        .unwrap_or_default();
+
+    Timestamps {
+        unix: unix_seconds,
+        iso8601,
+        epoch_offset: unix_seconds - EPOCH_OFFSET,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_at_epoch_offset_is_zero() {
+        let ts = normalize(EPOCH_OFFSET);
+
+        assert_eq!(ts.unix, EPOCH_OFFSET);
+        assert_eq!(ts.epoch_offset, 0);
+        assert_eq!(ts.iso8601, "2023-11-14T22:13:20+00:00");
+    }
+
+    #[test]
+    fn normalize_before_epoch_offset_is_negative() {
+        let ts = normalize(EPOCH_OFFSET - 1);
+
+        assert_eq!(ts.epoch_offset, -1);
+    }
+
+    #[test]
+    fn normalize_handles_unix_zero() {
+        let ts = normalize(0);
+
+        assert_eq!(ts.unix, 0);
+        assert_eq!(ts.epoch_offset, -EPOCH_OFFSET);
+        assert_eq!(ts.iso8601, "1970-01-01T00:00:00+00:00");
+    }
+
+    #[test]
+    fn normalize_handles_negative_unix_timestamps() {
+        let ts = normalize(-1);
+
+        assert_eq!(ts.unix, -1);
+        assert_eq!(ts.iso8601, "1969-12-31T23:59:59+00:00");
+    }
+}